@@ -0,0 +1,137 @@
+use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
+use near_sdk::collections::LookupMap;
+use near_sdk::serde::{Deserialize, Serialize};
+use near_sdk::{assert_one_yocto, env, near_bindgen, AccountId};
+
+use crate::config::Config;
+use crate::utils::REWARD_PER_SECOND;
+use crate::{Contract, ContractExt, OldContract, StorageKeys};
+
+/// Roles that can be granted to accounts on top of the implicit `owner_id` super-user.
+#[derive(
+    BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug,
+)]
+#[serde(crate = "near_sdk::serde")]
+#[serde(rename_all = "snake_case")]
+pub enum Role {
+    /// Can grant/revoke roles and pause/unpause the contract.
+    Admin,
+    /// Can pause/unpause the contract during an incident.
+    Pauser,
+    /// Reserved for reward-pool administration (staking rate, reward funding).
+    RewardManager,
+}
+
+impl Contract {
+    pub(crate) fn has_role(&self, account_id: &AccountId, role: &Role) -> bool {
+        self.roles
+            .get(account_id)
+            .map(|roles| roles.contains(role))
+            .unwrap_or(false)
+    }
+
+    /// Owner always passes; otherwise the caller must hold `role`.
+    pub(crate) fn assert_role(&self, role: Role) {
+        let account_id = env::predecessor_account_id();
+        assert!(
+            account_id == self.owner_id || self.has_role(&account_id, &role),
+            "Account @{} is missing the {:?} role",
+            account_id,
+            role
+        );
+    }
+
+    /// Owner, `Admin` and `Pauser` may pause/unpause.
+    fn assert_can_pause(&self) {
+        let account_id = env::predecessor_account_id();
+        assert!(
+            account_id == self.owner_id
+                || self.has_role(&account_id, &Role::Admin)
+                || self.has_role(&account_id, &Role::Pauser),
+            "Account @{} is missing the Admin or Pauser role",
+            account_id
+        );
+    }
+
+    pub(crate) fn require_not_paused(&self) {
+        assert!(!self.paused, "Contract is paused");
+    }
+}
+
+#[near_bindgen]
+impl Contract {
+    pub fn grant_role(&mut self, account_id: AccountId, role: Role) {
+        self.assert_role(Role::Admin);
+        let mut account_roles = self.roles.get(&account_id).unwrap_or_default();
+        account_roles.insert(role);
+        self.roles.insert(&account_id, &account_roles);
+    }
+
+    pub fn revoke_role(&mut self, account_id: AccountId, role: Role) {
+        self.assert_role(Role::Admin);
+        if let Some(mut account_roles) = self.roles.get(&account_id) {
+            account_roles.remove(&role);
+            self.roles.insert(&account_id, &account_roles);
+        }
+    }
+
+    pub fn has_role_view(&self, account_id: AccountId, role: Role) -> bool {
+        self.has_role(&account_id, &role)
+    }
+
+    #[payable]
+    pub fn pause(&mut self) {
+        assert_one_yocto();
+        self.assert_can_pause();
+        self.paused = true;
+    }
+
+    #[payable]
+    pub fn unpause(&mut self) {
+        assert_one_yocto();
+        self.assert_can_pause();
+        self.paused = false;
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// Reads the pre-upgrade state written by the previous contract version and maps it onto
+    /// the current layout. Only the owner may call this, and it has to run right after `deploy`:
+    /// until it does, the stored state is in the old layout and other calls fail to read it.
+    #[init(ignore_state)]
+    pub fn migrate() -> Self {
+        let old_state: OldContract = env::state_read().expect("Failed to read old state");
+        assert_eq!(
+            env::predecessor_account_id(),
+            old_state.owner_id,
+            "Only the owner can migrate the contract"
+        );
+
+        Self {
+            token: old_state.token,
+            metadata: old_state.metadata,
+            owner_id: old_state.owner_id,
+            user_reward_per_token_paid: old_state.user_reward_per_token_paid,
+            rewards: old_state.rewards,
+            balances: old_state.balances,
+            stake_monster_pct: old_state.stake_monster_pct,
+            zml_reserved: old_state.zml_reserved,
+            total_supply: old_state.total_supply,
+            last_update_time: old_state.last_update_time,
+            reward_per_token_stored: old_state.reward_per_token_stored,
+            roles: LookupMap::new(StorageKeys::Roles),
+            paused: false,
+            orders: LookupMap::new(StorageKeys::Orders),
+            next_order_id: 0,
+            bids: crate::market::new_order_book_side(StorageKeys::Bids),
+            asks: crate::market::new_order_book_side(StorageKeys::Asks),
+            // The reward pool starts empty post-migration; an admin must call `fund_rewards`
+            // to open a new (capped) reward period under the new tokenomics.
+            config: Config::default_with_reward_rate(REWARD_PER_SECOND),
+            reward_pool: 0,
+            period_finish: 0,
+        }
+    }
+}
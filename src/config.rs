@@ -0,0 +1,111 @@
+use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
+use near_sdk::json_types::U128;
+use near_sdk::serde::Serialize;
+use near_sdk::{env, near_bindgen};
+
+use crate::access::Role;
+use crate::utils::{nano_to_sec, ONE_TOKEN, U256};
+use crate::{Contract, ContractExt};
+
+const MAX_TRANSFER_COMMISSION_BPS: u16 = 1_000; // 10%
+const MAX_REWARD_PER_SECOND: u128 = ONE_TOKEN * 10;
+
+fn max_claim_deposit() -> u128 {
+    crate::utils::convert_to_yocto("10")
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct Config {
+    pub reward_per_second: U128,
+    pub transfer_commission_bps: u16,
+    pub claim_deposit: U128,
+}
+
+impl Config {
+    pub(crate) fn default_with_reward_rate(reward_per_second: u128) -> Self {
+        Self {
+            reward_per_second: reward_per_second.into(),
+            transfer_commission_bps: 50, // 0.5%, matches the previous hardcoded commission
+            claim_deposit: crate::utils::convert_to_yocto("0.1").into(),
+        }
+    }
+}
+
+#[near_bindgen]
+impl Contract {
+    /// Adds `amount` ZML (debited from the caller's own FT balance) to the reward pool and
+    /// (re)computes the reward rate so the pool is exhausted after `duration_seconds`. Rolls any
+    /// unpaid reward from the current period into the new one, matching the classic
+    /// fixed-duration staking-rewards distributor pattern.
+    pub fn fund_rewards(&mut self, amount: U128, duration_seconds: u64) {
+        self.assert_role(Role::RewardManager);
+        let amount = amount.0;
+        assert!(amount > 0, "Amount must be positive");
+        assert!(duration_seconds > 0, "Duration must be positive");
+
+        self.reward_per_token_stored = self.reward_per_token();
+        self.last_update_time = self.last_time_reward_applicable();
+
+        let admin = env::predecessor_account_id();
+        self.token.internal_withdraw(&admin, amount);
+        self.reward_pool += amount;
+
+        let now = env::block_timestamp();
+        let remaining = if now < self.period_finish {
+            let remaining_seconds = nano_to_sec(self.period_finish - now);
+            (U256::from(self.config.reward_per_second.0) * U256::from(remaining_seconds)).as_u128()
+        } else {
+            0
+        };
+
+        let reward_per_second = (amount + remaining) / duration_seconds as u128;
+        assert!(
+            reward_per_second <= MAX_REWARD_PER_SECOND,
+            "Funding this amount over duration_seconds would push reward_per_second past the allowed maximum"
+        );
+        self.config.reward_per_second = reward_per_second.into();
+        self.period_finish = now + duration_seconds * 1_000_000_000;
+        // Accrual for the new period starts now, not at the end of the previous one.
+        self.last_update_time = now;
+    }
+
+    pub fn set_reward_rate(&mut self, reward_per_second: U128) {
+        self.assert_role(Role::RewardManager);
+        assert!(
+            reward_per_second.0 <= MAX_REWARD_PER_SECOND,
+            "reward_per_second exceeds the allowed maximum"
+        );
+
+        self.reward_per_token_stored = self.reward_per_token();
+        self.last_update_time = self.last_time_reward_applicable();
+        self.config.reward_per_second = reward_per_second;
+    }
+
+    pub fn set_transfer_commission(&mut self, transfer_commission_bps: u16) {
+        self.assert_role(Role::RewardManager);
+        assert!(
+            transfer_commission_bps <= MAX_TRANSFER_COMMISSION_BPS,
+            "transfer_commission_bps exceeds the allowed maximum"
+        );
+        self.config.transfer_commission_bps = transfer_commission_bps;
+    }
+
+    pub fn set_claim_deposit(&mut self, claim_deposit: U128) {
+        self.assert_role(Role::RewardManager);
+        assert!(claim_deposit.0 <= max_claim_deposit(), "claim_deposit exceeds the allowed maximum");
+        self.config.claim_deposit = claim_deposit;
+    }
+
+    pub fn get_config(&self) -> Config {
+        self.config.clone()
+    }
+
+    pub fn get_reward_pool(&self) -> U128 {
+        self.reward_pool.into()
+    }
+
+    pub fn get_period_finish(&self) -> u64 {
+        self.period_finish
+    }
+}
@@ -0,0 +1,95 @@
+//! NEP-297 structured event logging.
+//!
+//! Events are logged as `EVENT_JSON:{...}` so off-chain indexers can reconstruct contract
+//! activity without parsing free-form log strings. The standard `nep141` events cover token
+//! supply changes; the custom `zomland` standard covers staking/reward/reserve flows that have
+//! no NEP equivalent.
+
+use near_sdk::json_types::U128;
+use near_sdk::serde_json::json;
+use near_sdk::{log, AccountId};
+
+const NEP141_STANDARD: &str = "nep141";
+const NEP141_VERSION: &str = "1.0.0";
+const ZOMLAND_STANDARD: &str = "zomland";
+const ZOMLAND_VERSION: &str = "1.0.0";
+
+fn emit(standard: &str, version: &str, event: &str, data: near_sdk::serde_json::Value) {
+    let payload = json!({
+        "standard": standard,
+        "version": version,
+        "event": event,
+        "data": [data],
+    });
+    log!("EVENT_JSON:{}", payload);
+}
+
+pub(crate) fn emit_ft_mint(owner_id: &AccountId, amount: U128) {
+    emit(NEP141_STANDARD, NEP141_VERSION, "ft_mint", json!({ "owner_id": owner_id, "amount": amount }));
+}
+
+pub(crate) fn emit_ft_transfer(old_owner_id: &AccountId, new_owner_id: &AccountId, amount: U128) {
+    emit(
+        NEP141_STANDARD,
+        NEP141_VERSION,
+        "ft_transfer",
+        json!({ "old_owner_id": old_owner_id, "new_owner_id": new_owner_id, "amount": amount }),
+    );
+}
+
+pub(crate) fn emit_ft_burn(owner_id: &AccountId, amount: U128) {
+    emit(NEP141_STANDARD, NEP141_VERSION, "ft_burn", json!({ "owner_id": owner_id, "amount": amount }));
+}
+
+pub(crate) fn emit_stake(account_id: &AccountId, amount: U128, total_supply: U128) {
+    emit(
+        ZOMLAND_STANDARD,
+        ZOMLAND_VERSION,
+        "stake",
+        json!({ "account_id": account_id, "amount": amount, "total_supply": total_supply }),
+    );
+}
+
+pub(crate) fn emit_unstake(account_id: &AccountId, amount: U128, total_supply: U128) {
+    emit(
+        ZOMLAND_STANDARD,
+        ZOMLAND_VERSION,
+        "unstake",
+        json!({ "account_id": account_id, "amount": amount, "total_supply": total_supply }),
+    );
+}
+
+pub(crate) fn emit_reward_claim(account_id: &AccountId, amount: U128) {
+    emit(ZOMLAND_STANDARD, ZOMLAND_VERSION, "reward_claim", json!({ "account_id": account_id, "amount": amount }));
+}
+
+pub(crate) fn emit_reserve_add(account_id: &AccountId, amount: U128) {
+    emit(ZOMLAND_STANDARD, ZOMLAND_VERSION, "reserve_add", json!({ "account_id": account_id, "amount": amount }));
+}
+
+pub(crate) fn emit_reserve_withdraw(account_id: &AccountId, amount: U128) {
+    emit(
+        ZOMLAND_STANDARD,
+        ZOMLAND_VERSION,
+        "reserve_withdraw",
+        json!({ "account_id": account_id, "amount": amount }),
+    );
+}
+
+pub(crate) fn emit_trade(taker_id: &AccountId, maker_id: &AccountId, price: U128, amount: U128) {
+    emit(
+        ZOMLAND_STANDARD,
+        ZOMLAND_VERSION,
+        "trade",
+        json!({ "taker_id": taker_id, "maker_id": maker_id, "price": price, "amount": amount }),
+    );
+}
+
+pub(crate) fn emit_monster_bonus_set(account_id: &AccountId, bonus_pct: u8) {
+    emit(
+        ZOMLAND_STANDARD,
+        ZOMLAND_VERSION,
+        "monster_bonus_set",
+        json!({ "account_id": account_id, "bonus_pct": bonus_pct }),
+    );
+}
@@ -25,9 +25,17 @@ use near_sdk::{AccountId, Balance, env, log, near_bindgen, PanicOnDefault, Promi
 use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
 use near_sdk::collections::{LazyOption, LookupMap};
 use near_sdk::json_types::{U128};
+use std::collections::HashSet;
 use std::convert::TryInto;
-use crate::utils::{ONE_TOKEN, assert_parent_contract};
-
+use crate::access::Role;
+use crate::config::Config;
+use crate::market::{Order, OrderBookSide};
+use crate::utils::{REWARD_PER_SECOND, assert_parent_contract};
+
+mod access;
+mod config;
+mod events;
+mod market;
 mod utils;
 mod staking;
 
@@ -41,6 +49,10 @@ pub enum StorageKeys {
     Balances,
     StakeMonsterPct,
     ZmlReserved,
+    Roles,
+    Orders,
+    Bids,
+    Asks,
 }
 
 #[derive(BorshDeserialize, BorshSerialize)]
@@ -72,30 +84,19 @@ pub struct Contract {
     total_supply: u128,
     last_update_time: u64,
     reward_per_token_stored: u128,
+    roles: LookupMap<AccountId, HashSet<Role>>,
+    paused: bool,
+    orders: LookupMap<u64, Order>,
+    next_order_id: u64,
+    bids: OrderBookSide,
+    asks: OrderBookSide,
+    config: Config,
+    reward_pool: Balance,
+    period_finish: u64,
 }
 
 #[near_bindgen]
 impl Contract {
-    // #[private]
-    // #[init(ignore_state)]
-    // pub fn migrate() -> Self {
-    //     let old_state: OldContract = env::state_read().expect("failed");
-    //
-    //     Self {
-    //         token: old_state.token,
-    //         metadata: old_state.metadata,
-    //         owner_id: old_state.owner_id,
-    //         user_reward_per_token_paid: old_state.user_reward_per_token_paid,
-    //         rewards: old_state.rewards,
-    //         balances: old_state.balances,
-    //         stake_monster_pct: old_state.stake_monster_pct,
-    //         zml_reserved: old_state.zml_reserved,
-    //         total_supply: old_state.total_supply,
-    //         last_update_time: old_state.last_update_time,
-    //         reward_per_token_stored: old_state.reward_per_token_stored,
-    //     }
-    // }
-
     #[init]
     pub fn new_default_meta(owner_id: AccountId, total_supply: U128) -> Self {
         Self::new(
@@ -134,6 +135,15 @@ impl Contract {
             total_supply: 0,
             last_update_time: env::block_timestamp(),
             reward_per_token_stored: 0,
+            roles: LookupMap::new(StorageKeys::Roles),
+            paused: false,
+            orders: LookupMap::new(StorageKeys::Orders),
+            next_order_id: 0,
+            bids: crate::market::new_order_book_side(StorageKeys::Bids),
+            asks: crate::market::new_order_book_side(StorageKeys::Asks),
+            config: Config::default_with_reward_rate(REWARD_PER_SECOND),
+            reward_pool: 0,
+            period_finish: 0,
         };
 
         // Leave 80 million tokens for staking in current contract
@@ -167,6 +177,8 @@ impl Contract {
         receiver_id: AccountId,
         amount: U128,
     ) {
+        self.require_not_paused();
+
         //get initial storage usage
         assert_eq!(amount.0, 0, "Cannot mint tokens, just 0 for approve");
 
@@ -190,6 +202,8 @@ impl Contract {
         if refund > 1 {
             Promise::new(env::predecessor_account_id()).transfer(refund);
         }
+
+        crate::events::emit_ft_mint(&receiver_id, amount);
     }
 
     pub(crate) fn add_zml_reserve(&mut self, account_id: &AccountId, amount: U128) {
@@ -199,6 +213,8 @@ impl Contract {
         let mut reserved = self.zml_reserved.get(account_id).unwrap_or(0);
         reserved += amount;
         self.zml_reserved.insert(account_id, &reserved);
+
+        crate::events::emit_reserve_add(account_id, amount.into());
     }
 
     pub fn get_zml_reserve(&self, account_id: &AccountId) -> U128 {
@@ -206,6 +222,7 @@ impl Contract {
     }
 
     pub fn burn_zml_reserve(&mut self, account_id: &AccountId, required_zml: U128) -> U128 {
+        self.require_not_paused();
         let main_contract = assert_parent_contract();
         let required_zml = required_zml.0;
         let mut reserved = self.zml_reserved.get(account_id).unwrap_or(0);
@@ -217,6 +234,8 @@ impl Contract {
             let burn_account_id = format!("burn.{}", main_contract).try_into().unwrap();
             self.token.internal_deposit(&burn_account_id, required_zml);
 
+            crate::events::emit_ft_burn(account_id, required_zml.into());
+
             return required_zml.into();
         } else {
             env::panic_str("Not enough ZML reserve");
@@ -224,6 +243,7 @@ impl Contract {
     }
 
     pub fn transfer_zml_reserve(&mut self, sender_id: &AccountId, receiver_id: &AccountId, required_zml: U128) -> U128 {
+        self.require_not_paused();
         let main_contract = assert_parent_contract();
         let required_zml = required_zml.0;
         let mut reserved = self.zml_reserved.get(sender_id).unwrap_or(0);
@@ -232,26 +252,87 @@ impl Contract {
             reserved -= required_zml;
             self.zml_reserved.insert(sender_id, &reserved);
 
-            let commission = 0.005;
-            let tax = (required_zml as f64 * commission) as u128;
-            let total = (required_zml - tax) as u128;
+            let tax = required_zml * self.config.transfer_commission_bps as u128 / 10_000;
+            let total = required_zml - tax;
 
             // transfer to recipient account
             self.token.internal_deposit(receiver_id, total);
             self.token.internal_deposit(&main_contract, tax);
 
+            crate::events::emit_ft_transfer(sender_id, receiver_id, total.into());
+
             return required_zml.into();
         } else {
             env::panic_str("Not enough ZML reserve");
         }
     }
 
+    /// Debits `sender_id`'s reserve exactly once for the summed total, then deposits to every
+    /// receiver in the same call. Panics atomically (before any deposit happens) if the reserve
+    /// can't cover the whole batch, so no partial state is committed.
+    pub fn batch_transfer_zml_reserve(
+        &mut self,
+        sender_id: &AccountId,
+        receivers: Vec<AccountId>,
+        amounts: Vec<U128>,
+    ) -> Vec<U128> {
+        self.require_not_paused();
+        let main_contract = assert_parent_contract();
+        assert_eq!(
+            receivers.len(),
+            amounts.len(),
+            "receivers and amounts must have the same length"
+        );
+        assert!(!receivers.is_empty(), "Provide at least one receiver");
+
+        let total: u128 = amounts.iter().map(|amount| amount.0).sum();
+        let mut reserved = self.zml_reserved.get(sender_id).unwrap_or(0);
+        assert!(reserved >= total, "Not enough ZML reserve");
+
+        reserved -= total;
+        self.zml_reserved.insert(sender_id, &reserved);
+
+        let commission_bps = self.config.transfer_commission_bps as u128;
+        let mut applied = Vec::with_capacity(receivers.len());
+        let mut total_tax = 0u128;
+
+        for (receiver_id, amount) in receivers.iter().zip(amounts.iter()) {
+            let amount = amount.0;
+            let tax = amount * commission_bps / 10_000;
+            let payout = amount - tax;
+
+            self.token.internal_deposit(receiver_id, payout);
+            total_tax += tax;
+
+            crate::events::emit_ft_transfer(sender_id, receiver_id, payout.into());
+            applied.push(U128(amount));
+        }
+
+        self.token.internal_deposit(&main_contract, total_tax);
+
+        applied
+    }
+
+    /// Tops up multiple players' reserves in one call, e.g. for clan reward splits.
+    pub fn batch_add_zml_reserve(&mut self, entries: Vec<(AccountId, U128)>) {
+        self.require_not_paused();
+        assert_parent_contract();
+        assert!(!entries.is_empty(), "Provide at least one entry");
+
+        for (account_id, amount) in entries.iter() {
+            self.add_zml_reserve(account_id, *amount);
+        }
+    }
+
     pub fn withdraw_zml_reserve(&mut self) {
+        self.require_not_paused();
         let account_id = env::predecessor_account_id();
         let reserved = self.zml_reserved.get(&account_id).unwrap_or(0);
         if reserved > 0 {
             self.zml_reserved.insert(&account_id, &0);
             self.token.internal_deposit(&account_id, reserved);
+
+            crate::events::emit_reserve_withdraw(&account_id, reserved.into());
         } else {
             env::panic_str("No reserved ZML");
         }
@@ -289,6 +370,13 @@ impl FungibleTokenReceiver for Contract {
                 self.add_zml_reserve(&sender_id, amount.into());
                 PromiseOrValue::Value(U128(0))
             }
+            msg if msg.starts_with("limit_sell:") => {
+                let price: u128 = msg["limit_sell:".len()..]
+                    .parse()
+                    .unwrap_or_else(|_| env::panic_str("Invalid limit_sell price"));
+                let unused = self.place_sell_order(sender_id, amount.0, price);
+                PromiseOrValue::Value(unused.into())
+            }
             _ => {
                 env::log_str("Invalid instruction for raffle call");
                 PromiseOrValue::Value(amount)
@@ -0,0 +1,333 @@
+use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
+use near_sdk::collections::TreeMap;
+use near_sdk::json_types::U128;
+use near_sdk::serde::Serialize;
+use near_sdk::{assert_one_yocto, env, near_bindgen, AccountId, Balance, Promise};
+
+use crate::events;
+use crate::utils::{ONE_TOKEN, U256};
+use crate::{Contract, ContractExt, StorageKeys};
+
+/// Minimum ZML amount allowed to rest as a sell order. `ft_on_transfer` has no channel for the
+/// maker to attach a NEAR storage deposit the way `place_buy_order` does, so a remainder below
+/// this is rejected (and refunded to the sender via the token standard's unused-amount
+/// resolution) instead of growing `orders`/`asks` storage for free.
+const MIN_RESTING_SELL_AMOUNT: u128 = ONE_TOKEN / 100;
+
+/// Conservative upper bound on the bytes a single resting order adds to `orders` plus its
+/// price-level queue entry in `bids`. Reserved out of `place_buy_order`'s deposit *before*
+/// `amount` is computed, so the order-escrow portion never eats the whole deposit and a bid can
+/// still actually rest.
+const ORDER_STORAGE_BYTES: u64 = 200;
+
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(crate = "near_sdk::serde")]
+#[serde(rename_all = "snake_case")]
+pub enum Side {
+    Buy,
+    Sell,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct Order {
+    pub maker: AccountId,
+    pub side: Side,
+    pub price: U128,
+    pub amount_remaining: U128,
+    /// NEAR charged to the maker to cover this resting slot's storage; refunded on cancel.
+    /// Always 0 for sell orders, which have no deposit to charge against.
+    pub storage_deposit: U128,
+}
+
+pub(crate) type OrderBookSide = TreeMap<u128, Vec<u64>>;
+
+/// `price * amount / ONE_TOKEN`, floored so a fill can never cost more NEAR than was escrowed.
+fn near_for_fill(price: u128, amount: u128) -> u128 {
+    (U256::from(price) * U256::from(amount) / U256::from(ONE_TOKEN)).as_u128()
+}
+
+impl Contract {
+    fn next_order_id(&mut self) -> u64 {
+        let id = self.next_order_id;
+        self.next_order_id += 1;
+        id
+    }
+
+    fn push_order(&mut self, side: Side, price: u128, id: u64) {
+        let tree = match side {
+            Side::Buy => &mut self.bids,
+            Side::Sell => &mut self.asks,
+        };
+        let mut queue = tree.get(&price).unwrap_or_default();
+        queue.push(id);
+        tree.insert(&price, &queue);
+    }
+
+    /// Removes `id` from its price-level FIFO queue, dropping the level if it's now empty.
+    /// Used when an order is cancelled.
+    fn remove_order_from_queue(&mut self, side: Side, price: u128, id: u64) {
+        let tree = match side {
+            Side::Buy => &mut self.bids,
+            Side::Sell => &mut self.asks,
+        };
+        if let Some(mut queue) = tree.get(&price) {
+            queue.retain(|queued_id| *queued_id != id);
+            if queue.is_empty() {
+                tree.remove(&price);
+            } else {
+                tree.insert(&price, &queue);
+            }
+        }
+    }
+
+    /// Fills `amount` of ZML at `sell_price` against resting bids, crediting NEAR to the seller
+    /// and ZML to each matched maker. Returns the amount still unmatched.
+    fn match_sell(&mut self, seller: &AccountId, sell_price: u128, mut amount: u128) -> u128 {
+        let mut price_cursor = self.bids.max();
+        while amount > 0 {
+            let best_bid_price = match price_cursor {
+                Some(price) if price >= sell_price => price,
+                _ => break,
+            };
+
+            let mut queue = self.bids.get(&best_bid_price).unwrap();
+            // Self-trades are rejected by skipping past the taker's own resting orders rather
+            // than panicking: a hard panic here would abort every fill already made earlier in
+            // this same call.
+            let Some(queue_index) = queue
+                .iter()
+                .position(|id| self.orders.get(id).map(|order| &order.maker != seller).unwrap_or(false))
+            else {
+                price_cursor = self.bids.lower(&best_bid_price);
+                continue;
+            };
+
+            let order_id = queue[queue_index];
+            let mut bid = self.orders.get(&order_id).unwrap();
+
+            let bid_remaining = bid.amount_remaining.0;
+            let fill = amount.min(bid_remaining);
+            let near_amount = near_for_fill(best_bid_price, fill);
+
+            self.token.internal_deposit(&bid.maker, fill);
+            Promise::new(seller.clone()).transfer(near_amount);
+            events::emit_trade(seller, &bid.maker, best_bid_price.into(), fill.into());
+
+            amount -= fill;
+            let bid_remaining = bid_remaining - fill;
+
+            if bid_remaining == 0 {
+                self.orders.remove(&order_id);
+                queue.remove(queue_index);
+                if queue.is_empty() {
+                    self.bids.remove(&best_bid_price);
+                    price_cursor = self.bids.lower(&best_bid_price);
+                } else {
+                    self.bids.insert(&best_bid_price, &queue);
+                }
+            } else {
+                bid.amount_remaining = bid_remaining.into();
+                self.orders.insert(&order_id, &bid);
+            }
+        }
+        amount
+    }
+
+    /// Fills `amount` of ZML at `buy_price` against resting asks, returning `(unmatched_amount,
+    /// near_spent)` so the caller can refund the difference between what was escrowed and what
+    /// was actually paid out at the (possibly better) resting ask price.
+    fn match_buy(&mut self, buyer: &AccountId, buy_price: u128, mut amount: u128) -> (u128, u128) {
+        let mut spent = 0u128;
+        let mut price_cursor = self.asks.min();
+        while amount > 0 {
+            let best_ask_price = match price_cursor {
+                Some(price) if price <= buy_price => price,
+                _ => break,
+            };
+
+            let mut queue = self.asks.get(&best_ask_price).unwrap();
+            // See match_sell: skip the taker's own resting orders instead of panicking, so a
+            // self-trade at one price level doesn't throw away fills already made this call.
+            let Some(queue_index) = queue
+                .iter()
+                .position(|id| self.orders.get(id).map(|order| &order.maker != buyer).unwrap_or(false))
+            else {
+                price_cursor = self.asks.higher(&best_ask_price);
+                continue;
+            };
+
+            let order_id = queue[queue_index];
+            let mut ask = self.orders.get(&order_id).unwrap();
+
+            let ask_remaining = ask.amount_remaining.0;
+            let fill = amount.min(ask_remaining);
+            let near_amount = near_for_fill(best_ask_price, fill);
+
+            self.token.internal_deposit(buyer, fill);
+            Promise::new(ask.maker.clone()).transfer(near_amount);
+            events::emit_trade(buyer, &ask.maker, best_ask_price.into(), fill.into());
+
+            amount -= fill;
+            spent += near_amount;
+            let ask_remaining = ask_remaining - fill;
+
+            if ask_remaining == 0 {
+                self.orders.remove(&order_id);
+                queue.remove(queue_index);
+                if queue.is_empty() {
+                    self.asks.remove(&best_ask_price);
+                    price_cursor = self.asks.higher(&best_ask_price);
+                } else {
+                    self.asks.insert(&best_ask_price, &queue);
+                }
+            } else {
+                ask.amount_remaining = ask_remaining.into();
+                self.orders.insert(&order_id, &ask);
+            }
+        }
+        (amount, spent)
+    }
+
+    /// Triggered via `ft_on_transfer` with `msg = "limit_sell:<price>"`. The ZML was already
+    /// credited to this contract's own balance by the transfer, so matching/resting only needs
+    /// to track the `Order`, not move tokens again. Returns the amount left unused, which
+    /// `ft_on_transfer` passes back up so the token standard refunds it to `seller`.
+    pub(crate) fn place_sell_order(&mut self, seller: AccountId, amount: u128, price: u128) -> u128 {
+        self.require_not_paused();
+        assert!(price > 0, "Price must be positive");
+        assert!(amount > 0, "Amount must be positive");
+
+        let remaining = self.match_sell(&seller, price, amount);
+
+        if remaining == 0 || remaining < MIN_RESTING_SELL_AMOUNT {
+            return remaining;
+        }
+
+        let id = self.next_order_id();
+        self.orders.insert(
+            &id,
+            &Order {
+                maker: seller,
+                side: Side::Sell,
+                price: price.into(),
+                amount_remaining: remaining.into(),
+                storage_deposit: 0.into(),
+            },
+        );
+        self.push_order(Side::Sell, price, id);
+        0
+    }
+}
+
+#[near_bindgen]
+impl Contract {
+    #[payable]
+    pub fn place_buy_order(&mut self, price: U128) -> Option<u64> {
+        self.require_not_paused();
+        let price = price.0;
+        assert!(price > 0, "Price must be positive");
+
+        let deposit = env::attached_deposit();
+        assert!(deposit > 0, "Attach NEAR to place a buy order");
+
+        // A resting order is a new storage slot; reserve its cost out of the deposit up front so
+        // `amount` is computed from the order-escrow portion only, not the whole deposit. The
+        // reserve is a conservative flat estimate — the actual charge (below) is never more.
+        let storage_reserve = env::storage_byte_cost() * Balance::from(ORDER_STORAGE_BYTES);
+        assert!(
+            deposit > storage_reserve,
+            "Attach more NEAR to cover both the order and its storage deposit"
+        );
+        let order_deposit = deposit - storage_reserve;
+
+        let buyer = env::predecessor_account_id();
+        let amount = (U256::from(order_deposit) * U256::from(ONE_TOKEN) / U256::from(price)).as_u128();
+        assert!(amount > 0, "Deposit too small for this price");
+
+        let (remaining, spent) = self.match_buy(&buyer, price, amount);
+        let escrow_for_resting = near_for_fill(price, remaining);
+
+        let initial_storage_usage = env::storage_usage();
+        let order_id = if remaining > 0 {
+            let id = self.next_order_id();
+            self.orders.insert(
+                &id,
+                &Order {
+                    maker: buyer.clone(),
+                    side: Side::Buy,
+                    price: price.into(),
+                    amount_remaining: remaining.into(),
+                    storage_deposit: storage_reserve.into(),
+                },
+            );
+            self.push_order(Side::Buy, price, id);
+            Some(id)
+        } else {
+            None
+        };
+        let storage_used = env::storage_usage() - initial_storage_usage;
+        // Capped at the reserve: ORDER_STORAGE_BYTES is a conservative upper bound, but cap
+        // defensively anyway so this can never underflow the refund below.
+        let storage_cost = (env::storage_byte_cost() * Balance::from(storage_used)).min(storage_reserve);
+        // Record what was actually charged, so `cancel_order` refunds exactly that.
+        if let Some(id) = order_id {
+            let mut order = self.orders.get(&id).unwrap();
+            order.storage_deposit = storage_cost.into();
+            self.orders.insert(&id, &order);
+        }
+
+        let refund = order_deposit - spent - escrow_for_resting + (storage_reserve - storage_cost);
+
+        if refund > 1 {
+            Promise::new(buyer).transfer(refund);
+        }
+
+        order_id
+    }
+
+    #[payable]
+    pub fn cancel_order(&mut self, id: u64) {
+        assert_one_yocto();
+        let order = self.orders.get(&id).expect("Order not found");
+        assert_eq!(
+            env::predecessor_account_id(),
+            order.maker,
+            "Only the maker can cancel this order"
+        );
+
+        self.orders.remove(&id);
+        self.remove_order_from_queue(order.side, order.price.0, id);
+
+        match order.side {
+            Side::Sell => {
+                self.token.internal_deposit(&order.maker, order.amount_remaining.0);
+                if order.storage_deposit.0 > 0 {
+                    Promise::new(order.maker).transfer(order.storage_deposit.0);
+                }
+            }
+            Side::Buy => {
+                let refund = near_for_fill(order.price.0, order.amount_remaining.0) + order.storage_deposit.0;
+                if refund > 0 {
+                    Promise::new(order.maker).transfer(refund);
+                }
+            }
+        }
+    }
+
+    pub fn get_order(&self, id: u64) -> Option<Order> {
+        self.orders.get(&id)
+    }
+
+    pub fn get_best_bid(&self) -> Option<U128> {
+        self.bids.max().map(Into::into)
+    }
+
+    pub fn get_best_ask(&self) -> Option<U128> {
+        self.asks.min().map(Into::into)
+    }
+}
+
+pub(crate) fn new_order_book_side(key: StorageKeys) -> OrderBookSide {
+    TreeMap::new(key)
+}
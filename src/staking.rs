@@ -1,17 +1,25 @@
 use crate::*;
 use near_sdk::json_types::U128;
 use near_sdk::{assert_one_yocto, env};
-use crate::utils::{U256, nano_to_sec, REWARD_PER_SECOND, assert_parent_contract, convert_to_yocto};
+use crate::utils::{ONE_TOKEN, U256, nano_to_sec, assert_parent_contract};
 
 
 impl Contract {
-    fn reward_per_token(&self) -> u128 {
-        if self.total_supply == 0 {
+    /// The accrual interval is clamped to `min(block_timestamp, period_finish)`, so once the
+    /// funded reward period ends `reward_per_token_stored` stops growing instead of accruing
+    /// against an empty pool.
+    pub(crate) fn last_time_reward_applicable(&self) -> u64 {
+        std::cmp::min(env::block_timestamp(), self.period_finish)
+    }
+
+    pub(crate) fn reward_per_token(&self) -> u128 {
+        let last_applicable = self.last_time_reward_applicable();
+        if self.total_supply == 0 || last_applicable <= self.last_update_time {
             return self.reward_per_token_stored;
         }
-        let seconds_diff = nano_to_sec(env::block_timestamp() - self.last_update_time);
+        let seconds_diff = nano_to_sec(last_applicable - self.last_update_time);
 
-        let reward = U256::from(seconds_diff) * U256::from(REWARD_PER_SECOND) * U256::from(ONE_TOKEN);
+        let reward = U256::from(seconds_diff) * U256::from(self.config.reward_per_second.0) * U256::from(ONE_TOKEN);
         self.reward_per_token_stored + (reward / U256::from(self.total_supply)).as_u128()
     }
 
@@ -34,7 +42,7 @@ impl Contract {
 
     fn update_reward(&mut self, account_id: &AccountId) {
         self.reward_per_token_stored = self.reward_per_token();
-        self.last_update_time = env::block_timestamp();
+        self.last_update_time = self.last_time_reward_applicable();
         self.rewards.insert(account_id, &self.earned(account_id));
         self.user_reward_per_token_paid.insert(account_id, &self.reward_per_token_stored);
     }
@@ -43,6 +51,7 @@ impl Contract {
 #[near_bindgen]
 impl Contract {
     pub fn internal_stake(&mut self, account_id: &AccountId, amount: U128) {
+        self.require_not_paused();
         let amount = amount.0;
         assert!(amount > 0, "Please specify staking amount");
 
@@ -52,11 +61,14 @@ impl Contract {
         user_balance += amount;
         self.balances.insert(account_id, &user_balance);
         self.total_supply += amount;
+
+        crate::events::emit_stake(account_id, amount.into(), self.total_supply.into());
     }
 
     #[payable]
     pub fn withdraw_stake(&mut self, amount: U128) {
         assert_one_yocto();
+        self.require_not_paused();
         let mut amount = amount.0;
         let account_id = env::predecessor_account_id();
         self.update_reward(&account_id);
@@ -72,11 +84,14 @@ impl Contract {
         self.total_supply -= amount;
 
         self.token.internal_deposit(&account_id, amount);
+
+        crate::events::emit_unstake(&account_id, amount.into(), self.total_supply.into());
     }
 
     #[payable]
     pub fn withdraw_reward(&mut self) {
-        if env::attached_deposit() < convert_to_yocto("0.1") {
+        self.require_not_paused();
+        if env::attached_deposit() < self.config.claim_deposit.0 {
             env::panic_str("Attach claim deposit!");
         }
 
@@ -88,20 +103,32 @@ impl Contract {
             env::panic_str("You don't have rewards");
         }
 
-        self.rewards.insert(&account_id, &0);
-        self.token.internal_deposit(&account_id, reward);
+        // Pay out of the funded pool only; any amount the pool can't cover stays owed and is
+        // claimable once the pool is topped up again via `fund_rewards`.
+        let payout = reward.min(self.reward_pool);
+        assert!(payout > 0, "Reward pool is empty, ask an admin to fund rewards");
+
+        self.rewards.insert(&account_id, &(reward - payout));
+        self.reward_pool -= payout;
+        self.token.internal_deposit(&account_id, payout);
+
+        crate::events::emit_reward_claim(&account_id, payout.into());
     }
 
     pub fn stake_monster(&mut self, bonus_pct: u8, account_id: AccountId) {
         assert_parent_contract();
         self.update_reward(&account_id);
         self.stake_monster_pct.insert(&account_id, &bonus_pct);
+
+        crate::events::emit_monster_bonus_set(&account_id, bonus_pct);
     }
 
     pub fn unstake_monster(&mut self, account_id: AccountId) {
         assert_parent_contract();
         self.update_reward(&account_id);
         self.stake_monster_pct.remove(&account_id);
+
+        crate::events::emit_monster_bonus_set(&account_id, 0);
     }
 
     pub fn get_total_supply(&self) -> U128 {
@@ -120,7 +147,7 @@ impl Contract {
     pub fn get_apr(&self) -> U128 {
         if self.total_supply > 0 {
             let year_seconds = 60 * 60 * 24 * 365;
-            let apr = (U256::from(REWARD_PER_SECOND) * U256::from(year_seconds) * U256::from(100) / U256::from(self.total_supply)).as_u128();
+            let apr = (U256::from(self.config.reward_per_second.0) * U256::from(year_seconds) * U256::from(100) / U256::from(self.total_supply)).as_u128();
             return apr.into();
         }
         0.into()
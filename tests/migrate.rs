@@ -0,0 +1,122 @@
+use near_sdk::json_types::U128;
+use near_units::parse_near;
+use workspaces::{Account, Contract};
+
+const OLD_WASM_FILEPATH: &str = "./tests/res/zomland_ft_old.wasm";
+const WASM_FILEPATH: &str = "../target/wasm32-unknown-unknown/release/zomland_ft.wasm";
+
+async fn deploy_old(worker: &workspaces::Worker<workspaces::network::Sandbox>) -> (Contract, Account) {
+    let wasm = std::fs::read(OLD_WASM_FILEPATH).unwrap();
+    let contract = worker.dev_deploy(&wasm).await.unwrap();
+    let owner = worker.dev_create_account().await.unwrap();
+
+    owner
+        .call(contract.id(), "new_default_meta")
+        .args_json((owner.id(), U128(1_000_000 * 10u128.pow(24))))
+        .transact()
+        .await
+        .unwrap()
+        .into_result()
+        .unwrap();
+
+    (contract, owner)
+}
+
+#[tokio::test]
+async fn test_migrate_preserves_stake_and_leaves_contract_unpaused() {
+    let worker = workspaces::sandbox().await.unwrap();
+    let (contract, owner) = deploy_old(&worker).await;
+
+    let staker = worker.dev_create_account().await.unwrap();
+    owner
+        .call(contract.id(), "storage_deposit")
+        .args_json((staker.id(), Option::<bool>::None))
+        .deposit(parse_near!("0.00125 N"))
+        .transact()
+        .await
+        .unwrap()
+        .into_result()
+        .unwrap();
+
+    // `new` credits the whole supply to the contract's own account, so fund the staker from it.
+    contract
+        .as_account()
+        .call(contract.id(), "ft_transfer")
+        .args_json((staker.id(), U128(1_000 * 10u128.pow(24)), Option::<String>::None))
+        .deposit(1)
+        .transact()
+        .await
+        .unwrap()
+        .into_result()
+        .unwrap();
+
+    staker
+        .call(contract.id(), "ft_transfer_call")
+        .args_json((contract.id(), U128(1_000 * 10u128.pow(24)), Option::<String>::None, "ft_staking"))
+        .deposit(1)
+        .max_gas()
+        .transact()
+        .await
+        .unwrap()
+        .into_result()
+        .unwrap();
+
+    let stake_before: U128 = owner
+        .call(contract.id(), "get_user_stake")
+        .args_json((staker.id(),))
+        .view()
+        .await
+        .unwrap()
+        .json()
+        .unwrap();
+    assert_eq!(stake_before, U128(1_000 * 10u128.pow(24)));
+
+    let new_wasm = std::fs::read(WASM_FILEPATH).unwrap();
+    contract.as_account().deploy(&new_wasm).await.unwrap().into_result().unwrap();
+
+    owner
+        .call(contract.id(), "migrate")
+        .transact()
+        .await
+        .unwrap()
+        .into_result()
+        .unwrap();
+
+    let stake_after: U128 = owner
+        .call(contract.id(), "get_user_stake")
+        .args_json((staker.id(),))
+        .view()
+        .await
+        .unwrap()
+        .json()
+        .unwrap();
+
+    assert_eq!(stake_before, stake_after);
+
+    let paused: bool = owner
+        .call(contract.id(), "is_paused")
+        .view()
+        .await
+        .unwrap()
+        .json()
+        .unwrap();
+    assert!(!paused);
+}
+
+#[tokio::test]
+async fn test_migrate_rejects_non_owner() {
+    let worker = workspaces::sandbox().await.unwrap();
+    let (contract, _owner) = deploy_old(&worker).await;
+    let stranger = worker.dev_create_account().await.unwrap();
+
+    let new_wasm = std::fs::read(WASM_FILEPATH).unwrap();
+    contract.as_account().deploy(&new_wasm).await.unwrap().into_result().unwrap();
+
+    let outcome = stranger
+        .call(contract.id(), "migrate")
+        .transact()
+        .await
+        .unwrap();
+
+    assert!(outcome.is_failure());
+}